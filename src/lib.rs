@@ -5,14 +5,32 @@
     fn_traits
 )]
 
-pub mod tiktaktoe;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    marker::Tuple,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use std::{collections::HashMap, hash::Hash, marker::Tuple};
+use dashmap::DashMap;
+use rayon::prelude::*;
 
 pub trait State {
     type Decision;
     fn decisions(&self) -> impl Iterator<Item = Self::Decision>;
     fn choose(&self, decision: Self::Decision) -> Self;
+
+    /// probability of `decision` being the one taken from this state, used to weight
+    /// `Evaluation::Chance` nodes and the expected-value half of the minimax/expectation
+    /// blend. Defaults to uniform over the legal decisions; override it for games whose
+    /// moves aren't equally likely (dice, weighted draws, ...).
+    fn probability(&self, _decision: &Self::Decision) -> f64 {
+        let count = self.decisions().count();
+        if count == 0 { 0.0 } else { 1.0 / count as f64 }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -26,6 +44,9 @@ pub enum Evaluation {
     Mode(Mode),
     ModeWithValue(Mode, f64),
     Value(f64),
+    /// a pure chance node (dice roll, card draw, ...): its value is the
+    /// probability-weighted average of its children, regardless of `ratio`.
+    Chance,
 }
 
 pub trait Eval<State> {
@@ -41,6 +62,216 @@ where
     }
 }
 
+/// whether a transposition-table entry holds the node's true value or only a bound
+/// produced by a search that was cut short by alpha-beta pruning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Flag {
+    Exact,
+    LowerBound,
+    UpperBound,
+}
+
+#[derive(Debug, Clone)]
+struct TtEntry<D> {
+    value: f64,
+    flag: Flag,
+    depth: u32,
+    /// the child decision that produced `value`, so a principal variation can be
+    /// reconstructed by walking these back-pointers from the root. `None` for leaves
+    /// and for `Evaluation::Chance` nodes, which have no single "best" child.
+    best: Option<D>,
+}
+
+/// depth at which [`alpha_beta`] treats every node as unbounded, i.e. searches to
+/// terminal states exactly as the original full-width search did.
+const UNBOUNDED_DEPTH: u32 = u32::MAX;
+
+/// alpha-beta minimax search backed by a transposition table, used for the pure
+/// minimax case (`ratio == 1.0`) where `eval_helper`'s full expansion is wasteful.
+///
+/// a hit is reused outright when it's `Exact`, or when its bound already proves
+/// the node is outside the current `[alpha, beta]` window, but only if it was
+/// searched to at least `depth`; otherwise the search falls through and
+/// re-explores with the narrowed window.
+///
+/// once `depth` reaches zero the node is cut off and evaluated as a leaf: the
+/// caller's `Eval` is still consulted, but its value (from `Evaluation::Value`
+/// or the bias of `Evaluation::ModeWithValue`) is taken directly as a static
+/// heuristic instead of expanding the node's children.
+///
+/// `deadline`, when set, is checked cheaply at the top of every call; once it
+/// has passed the search aborts by returning `None`, which propagates all the
+/// way back up without ever touching `table`, so a timed-out pass can't leave
+/// behind a partial, untrustworthy entry.
+fn alpha_beta<S, E>(
+    state: S,
+    eval: &E,
+    mut alpha: f64,
+    mut beta: f64,
+    depth: u32,
+    table: &mut HashMap<S, TtEntry<S::Decision>>,
+    deadline: Option<Instant>,
+) -> Option<f64>
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone,
+    E: Eval<S>,
+{
+    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+        return None;
+    }
+
+    let (searched_alpha, searched_beta) = (alpha, beta);
+
+    if let Some(entry) = table.get(&state) {
+        if entry.depth >= depth {
+            match entry.flag {
+                Flag::Exact => return Some(entry.value),
+                Flag::LowerBound if entry.value >= beta => return Some(entry.value),
+                Flag::UpperBound if entry.value <= alpha => return Some(entry.value),
+                _ => {}
+            }
+        }
+    }
+
+    let evaluation = eval.evaluate(&state);
+    let (value, flag, best) = match evaluation {
+        Evaluation::Value(value) => (value, Flag::Exact, None),
+        Evaluation::Chance if depth == 0 => panic!("{HORIZON_WITHOUT_VALUE}"),
+        Evaluation::Mode(_) if depth == 0 => panic!("{HORIZON_WITHOUT_VALUE}"),
+        Evaluation::ModeWithValue(_, value) if depth == 0 => (value, Flag::Exact, None),
+        Evaluation::Chance => {
+            // an expectation isn't bounded the way a minimax value is, so children are
+            // searched with a full window rather than the narrowed `alpha`/`beta`, and
+            // there's no single "best" child to record a back-pointer to.
+            let mut value = 0.0;
+            for decision in state.decisions() {
+                let probability = state.probability(&decision);
+                let child_value = alpha_beta(
+                    state.choose(decision),
+                    eval,
+                    f64::NEG_INFINITY,
+                    f64::INFINITY,
+                    depth - 1,
+                    table,
+                    deadline,
+                )?;
+                value += probability * child_value;
+            }
+            (value, Flag::Exact, None)
+        }
+        Evaluation::Mode(mode) | Evaluation::ModeWithValue(mode, _) => {
+            let bias = match evaluation {
+                Evaluation::ModeWithValue(_, value) => value,
+                _ => 0.0,
+            };
+            // children are searched in a window shifted by `bias`, so that once `bias` is
+            // added back the value can be checked against the caller's own window.
+            alpha -= bias;
+            beta -= bias;
+            let mut value = match mode {
+                Mode::Maximize => f64::NEG_INFINITY,
+                Mode::Minimize => f64::INFINITY,
+            };
+            let mut best = None;
+            for decision in state.decisions() {
+                let child_value = alpha_beta(
+                    state.choose(decision.clone()),
+                    eval,
+                    alpha,
+                    beta,
+                    depth - 1,
+                    table,
+                    deadline,
+                )?;
+                let improved = match mode {
+                    Mode::Maximize => child_value > value,
+                    Mode::Minimize => child_value < value,
+                };
+                if improved {
+                    best = Some(decision);
+                }
+                match mode {
+                    Mode::Maximize => {
+                        value = value.max(child_value);
+                        alpha = alpha.max(value);
+                    }
+                    Mode::Minimize => {
+                        value = value.min(child_value);
+                        beta = beta.min(value);
+                    }
+                }
+                if alpha >= beta {
+                    break;
+                }
+            }
+            value += bias;
+            let flag = if value <= searched_alpha {
+                Flag::UpperBound
+            } else if value >= searched_beta {
+                Flag::LowerBound
+            } else {
+                Flag::Exact
+            };
+            (value, flag, best)
+        }
+    };
+
+    table.insert(
+        state,
+        TtEntry {
+            value,
+            flag,
+            depth,
+            best,
+        },
+    );
+    Some(value)
+}
+
+/// walks a chain of [`TtEntry::best`] back-pointers starting from `state`'s entry in
+/// `table`, collecting the decision taken at each step to reconstruct the principal
+/// variation: the sequence of moves the search considers best for both sides. Stops as
+/// soon as a state has no entry, its entry has no recorded `best` (a leaf, or a
+/// `Evaluation::Chance` node), or a state reappears: `State` makes no promise that
+/// positions can't transpose into one another, so without this guard a cycle in `best`
+/// would walk forever.
+fn reconstruct_pv<S>(mut state: S, table: &HashMap<S, TtEntry<S::Decision>>) -> Vec<S::Decision>
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone,
+{
+    let mut pv = Vec::new();
+    let mut visited = HashSet::new();
+    visited.insert(state.clone());
+    while let Some(decision) = table.get(&state).and_then(|entry| entry.best.clone()) {
+        state = state.choose(decision.clone());
+        if !visited.insert(state.clone()) {
+            break;
+        }
+        pv.push(decision);
+    }
+    pv
+}
+
+/// total order over `(value, decision)` pairs used to pick the best root move
+/// deterministically: `f64::partial_cmp` can't order NaN, so a NaN value is treated as
+/// worse than any real number for either player, and if two decisions still tie on
+/// value, `S::Decision`'s own `Ord` impl breaks the tie so the same position always
+/// yields the same move instead of whichever happened to come last from `.max_by`.
+fn rank_decision<D: Ord>(
+    (left_decision, left_value): &(D, f64),
+    (right_decision, right_value): &(D, f64),
+) -> Ordering {
+    match (left_value.is_nan(), right_value.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => left_value.partial_cmp(right_value).unwrap(),
+    }
+    .then_with(|| left_decision.cmp(right_decision))
+}
+
 /// cache takes a recursive function f and returns a new function that memoizes the results.
 ///
 /// The function f is expected to accept as its first argument a recursive “call‐back”
@@ -78,7 +309,7 @@ where
 pub fn choose<S, E: Eval<S>>(eval: E, ratio: f64) -> impl FnMut(S) -> Option<(S::Decision, f64)>
 where
     S: State + Clone + Eq + Hash,
-    S::Decision: Clone + Eq + Hash,
+    S::Decision: Clone + Eq + Hash + Ord,
 {
     pub fn eval_helper<S: State>(
         state: S,
@@ -89,15 +320,21 @@ where
     ) -> f64 {
         let (minmax, expecto) = state
             .decisions()
-            .map(|decision| eval(state.choose(decision)))
-            .fold((fold_value, 0.0), |(f, sum), value| {
-                (fold(f, value), sum + value)
+            .map(|decision| {
+                let probability = state.probability(&decision);
+                (probability, eval(state.choose(decision)))
+            })
+            .fold((fold_value, 0.0), |(f, sum), (probability, value)| {
+                (fold(f, value), sum + probability * value)
             });
         ratio * minmax + (1.0 - ratio) * expecto
     }
 
-    let mut evaluate = cache(move |evaluate, state: S| match eval.evaluate(&state) {
+    let eval = Rc::new(eval);
+    let blend_eval = eval.clone();
+    let mut evaluate = cache(move |evaluate, state: S| match blend_eval.evaluate(&state) {
         Evaluation::Value(value) => value,
+        Evaluation::Chance => chance_helper(state, evaluate),
         Evaluation::Mode(Mode::Maximize) => {
             eval_helper(state, evaluate, f64::NEG_INFINITY, f64::max, ratio)
         }
@@ -111,10 +348,820 @@ where
             eval_helper(state, evaluate, f64::INFINITY, f64::min, ratio) + value
         }
     });
+
+    let mut table: HashMap<S, TtEntry<S::Decision>> = HashMap::new();
+
+    move |state| {
+        if ratio >= 1.0 {
+            state
+                .decisions()
+                .map(|decision| {
+                    let value = alpha_beta(
+                        state.choose(decision.clone()),
+                        &*eval,
+                        f64::NEG_INFINITY,
+                        f64::INFINITY,
+                        UNBOUNDED_DEPTH,
+                        &mut table,
+                        None,
+                    )
+                    .expect("search without a deadline cannot abort");
+                    (decision, value)
+                })
+                .max_by(rank_decision)
+        } else {
+            state
+                .decisions()
+                .map(|decision| (decision.clone(), evaluate(state.choose(decision))))
+                .max_by(rank_decision)
+        }
+    }
+}
+
+/// probability-weighted average of a node's children, used for `Evaluation::Chance`
+/// nodes, whose value is an expectation regardless of `ratio`.
+fn chance_helper<S: State>(state: S, mut eval: impl FnMut(S) -> f64) -> f64 {
+    state.decisions().fold(0.0, |sum, decision| {
+        let probability = state.probability(&decision);
+        sum + probability * eval(state.choose(decision))
+    })
+}
+
+/// message explaining why a depth-limited search can't treat a bare `Mode`/`Chance`
+/// horizon node as worth zero: unlike [`alpha_beta`]'s unbounded search, a depth-limited
+/// one needs a static heuristic the moment it stops expanding, and `Eval::evaluate`
+/// isn't told how much depth remains, so it can't supply one on its own. An evaluator
+/// meant for depth-limited search must itself return `Evaluation::Value` (or
+/// `Evaluation::ModeWithValue`'s bias) for any node where it wants the search to stop.
+const HORIZON_WITHOUT_VALUE: &str = "depth-limited search hit its horizon at a node whose \
+    Eval returned Evaluation::Mode or Evaluation::Chance with no static value; an Eval used \
+    with a depth-limited search must return Evaluation::Value (or ModeWithValue's bias) for \
+    any node where it wants the search to stop, since Eval::evaluate isn't told how much \
+    depth remains";
+
+/// depth-aware, deadline-aware counterpart of [`choose`]'s blended `eval_helper`/cache,
+/// shared by [`choose_to_depth`], [`iterative_deepening`], and [`choose_within`]: blends
+/// minimax and expected value via `ratio` exactly like [`choose`]'s own `evaluate`, but
+/// folds `depth` into its own memo table's key and cuts a node off as a leaf once `depth`
+/// reaches zero, panicking rather than silently scoring it `0.0` if the caller's `Eval`
+/// hasn't supplied a static value there.
+///
+/// manages its own table directly, the same way [`alpha_beta`] does, instead of going
+/// through the generic [`cache`] combinator: `deadline`, when set, is checked cheaply at
+/// the top of every call, and a call that aborts propagates `None` all the way back up
+/// without touching `table`, so — just like `alpha_beta` — a timed-out pass can't leave
+/// behind a partial, untrustworthy entry that a later, unhurried pass would wrongly reuse.
+fn depth_limited_evaluate<S, E>(
+    state: S,
+    eval: &E,
+    depth: u32,
+    ratio: f64,
+    table: &mut HashMap<(S, u32), f64>,
+    deadline: Option<Instant>,
+) -> Option<f64>
+where
+    S: State + Clone + Eq + Hash,
+    E: Eval<S>,
+{
+    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+        return None;
+    }
+
+    let key = (state.clone(), depth);
+    if let Some(&value) = table.get(&key) {
+        return Some(value);
+    }
+
+    let evaluation = eval.evaluate(&state);
+    let value = match evaluation {
+        Evaluation::Value(value) => value,
+        Evaluation::Chance if depth == 0 => panic!("{HORIZON_WITHOUT_VALUE}"),
+        Evaluation::Mode(_) if depth == 0 => panic!("{HORIZON_WITHOUT_VALUE}"),
+        Evaluation::ModeWithValue(_, value) if depth == 0 => value,
+        Evaluation::Chance => {
+            let mut value = 0.0;
+            for decision in state.decisions() {
+                let probability = state.probability(&decision);
+                let child =
+                    depth_limited_evaluate(state.choose(decision), eval, depth - 1, ratio, table, deadline)?;
+                value += probability * child;
+            }
+            value
+        }
+        Evaluation::Mode(mode) | Evaluation::ModeWithValue(mode, _) => {
+            let bias = match evaluation {
+                Evaluation::ModeWithValue(_, value) => value,
+                _ => 0.0,
+            };
+            let fold_value = match mode {
+                Mode::Maximize => f64::NEG_INFINITY,
+                Mode::Minimize => f64::INFINITY,
+            };
+            let fold: fn(f64, f64) -> f64 = match mode {
+                Mode::Maximize => f64::max,
+                Mode::Minimize => f64::min,
+            };
+            let mut minmax = fold_value;
+            let mut expecto = 0.0;
+            for decision in state.decisions() {
+                let probability = state.probability(&decision);
+                let child =
+                    depth_limited_evaluate(state.choose(decision), eval, depth - 1, ratio, table, deadline)?;
+                minmax = fold(minmax, child);
+                expecto += probability * child;
+            }
+            ratio * minmax + (1.0 - ratio) * expecto + bias
+        }
+    };
+
+    table.insert(key, value);
+    Some(value)
+}
+
+/// like [`choose`], but bounds the search to `max_depth` plies instead of recursing to
+/// terminal states. Once the horizon is reached, the node is evaluated as a leaf using
+/// whatever static value the caller's `Eval` supplies there (see [`alpha_beta`] for the
+/// pure-minimax case) instead of being expanded further.
+pub fn choose_to_depth<S, E: Eval<S>>(
+    eval: E,
+    ratio: f64,
+    max_depth: u32,
+) -> impl FnMut(S) -> Option<(S::Decision, f64)>
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone + Eq + Hash + Ord,
+{
+    let eval = Rc::new(eval);
+    let mut table: HashMap<S, TtEntry<S::Decision>> = HashMap::new();
+    let mut evaluate_table: HashMap<(S, u32), f64> = HashMap::new();
+
+    move |state| {
+        if ratio >= 1.0 {
+            state
+                .decisions()
+                .map(|decision| {
+                    let value = alpha_beta(
+                        state.choose(decision.clone()),
+                        &*eval,
+                        f64::NEG_INFINITY,
+                        f64::INFINITY,
+                        max_depth.saturating_sub(1),
+                        &mut table,
+                        None,
+                    )
+                    .expect("search without a deadline cannot abort");
+                    (decision, value)
+                })
+                .max_by(rank_decision)
+        } else {
+            state
+                .decisions()
+                .map(|decision| {
+                    let value = depth_limited_evaluate(
+                        state.choose(decision.clone()),
+                        &*eval,
+                        max_depth.saturating_sub(1),
+                        ratio,
+                        &mut evaluate_table,
+                        None,
+                    )
+                    .expect("search without a deadline cannot abort");
+                    (decision, value)
+                })
+                .max_by(rank_decision)
+        }
+    }
+}
+
+/// runs [`choose_to_depth`]'s search for `d = 1, 2, ..., max_depth`, sharing its caches
+/// across every pass so each shallower iteration primes the next, deeper one: the
+/// transposition table carries over alpha-beta bounds when `ratio == 1.0`, and
+/// [`depth_limited_evaluate`]'s `(state, depth)` memo table carries over shared
+/// subtrees between root decisions otherwise.
+pub fn iterative_deepening<S, E: Eval<S>>(
+    eval: E,
+    ratio: f64,
+    max_depth: u32,
+) -> impl FnMut(S) -> Option<(S::Decision, f64)>
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone + Eq + Hash + Ord,
+{
+    let eval = Rc::new(eval);
+    let mut table: HashMap<S, TtEntry<S::Decision>> = HashMap::new();
+    let mut evaluate_table: HashMap<(S, u32), f64> = HashMap::new();
+
+    move |state| {
+        let mut best = None;
+        for depth in 1..=max_depth {
+            best = state
+                .decisions()
+                .map(|decision| {
+                    let value = if ratio >= 1.0 {
+                        alpha_beta(
+                            state.choose(decision.clone()),
+                            &*eval,
+                            f64::NEG_INFINITY,
+                            f64::INFINITY,
+                            depth - 1,
+                            &mut table,
+                            None,
+                        )
+                        .expect("search without a deadline cannot abort")
+                    } else {
+                        depth_limited_evaluate(
+                            state.choose(decision.clone()),
+                            &*eval,
+                            depth - 1,
+                            ratio,
+                            &mut evaluate_table,
+                            None,
+                        )
+                        .expect("search without a deadline cannot abort")
+                    };
+                    (decision, value)
+                })
+                .max_by(rank_decision);
+        }
+        best
+    }
+}
+
+/// like [`iterative_deepening`], but runs to a wall-clock `budget` instead of a fixed
+/// `max_depth`, returning the best move found before time runs out.
+///
+/// each depth is only started if there's still budget left, and either way — `ratio ==
+/// 1.0` via [`alpha_beta`], `ratio < 1.0` via [`depth_limited_evaluate`] — the search
+/// also checks the deadline cheaply inside its own recursion and aborts by returning
+/// `None` without touching its table; either way, a pass that doesn't finish never
+/// overwrites `best`, so the result is always the root move of a consistent,
+/// fully-searched depth.
+pub fn choose_within<S, E: Eval<S>>(
+    eval: E,
+    ratio: f64,
+    budget: Duration,
+) -> impl FnMut(S) -> Option<(S::Decision, f64)>
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone + Eq + Hash + Ord,
+{
+    let eval = Rc::new(eval);
+    let mut table: HashMap<S, TtEntry<S::Decision>> = HashMap::new();
+    let mut evaluate_table: HashMap<(S, u32), f64> = HashMap::new();
+
+    move |state| {
+        let deadline = Instant::now() + budget;
+        let mut best = None;
+        for depth in 1.. {
+            if Instant::now() >= deadline {
+                break;
+            }
+
+            let pass = if ratio >= 1.0 {
+                state
+                    .decisions()
+                    .map(|decision| {
+                        alpha_beta(
+                            state.choose(decision.clone()),
+                            &*eval,
+                            f64::NEG_INFINITY,
+                            f64::INFINITY,
+                            depth - 1,
+                            &mut table,
+                            Some(deadline),
+                        )
+                        .map(|value| (decision, value))
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .and_then(|values| {
+                        values
+                            .into_iter()
+                            .max_by(rank_decision)
+                    })
+            } else {
+                state
+                    .decisions()
+                    .map(|decision| {
+                        depth_limited_evaluate(
+                            state.choose(decision.clone()),
+                            &*eval,
+                            depth - 1,
+                            ratio,
+                            &mut evaluate_table,
+                            Some(deadline),
+                        )
+                        .map(|value| (decision, value))
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .and_then(|values| {
+                        values
+                            .into_iter()
+                            .max_by(rank_decision)
+                    })
+            };
+
+            match pass {
+                Some(result) => best = Some(result),
+                None => break,
+            }
+        }
+        best
+    }
+}
+
+/// thread-safe counterpart of the plain `HashMap<S, TtEntry>` transposition table:
+/// `DashMap` shards its buckets across independent locks, so several [`choose_parallel`]
+/// workers can read and write it concurrently without serializing on a single mutex.
+type ConcurrentTable<S> = DashMap<S, TtEntry<<S as State>::Decision>>;
+
+/// same search as [`alpha_beta`], but against a [`ConcurrentTable`] shared behind an
+/// `Arc` instead of a `&mut HashMap`, so independent root searches still see (and prune
+/// against) subtrees discovered by other threads. Doesn't bother recording a `best`
+/// back-pointer the way [`alpha_beta`] does: [`choose_parallel`] has no use for a
+/// principal variation, and extracting one across several threads' interleaved writes
+/// to the same table wouldn't mean much anyway.
+fn alpha_beta_concurrent<S, E>(
+    state: S,
+    eval: &E,
+    mut alpha: f64,
+    mut beta: f64,
+    depth: u32,
+    table: &ConcurrentTable<S>,
+) -> f64
+where
+    S: State + Clone + Eq + Hash,
+    E: Eval<S>,
+{
+    let (searched_alpha, searched_beta) = (alpha, beta);
+
+    if let Some(entry) = table.get(&state) {
+        if entry.depth >= depth {
+            match entry.flag {
+                Flag::Exact => return entry.value,
+                Flag::LowerBound if entry.value >= beta => return entry.value,
+                Flag::UpperBound if entry.value <= alpha => return entry.value,
+                _ => {}
+            }
+        }
+    }
+
+    let evaluation = eval.evaluate(&state);
+    let (value, flag) = match evaluation {
+        Evaluation::Value(value) => (value, Flag::Exact),
+        Evaluation::Chance if depth == 0 => panic!("{HORIZON_WITHOUT_VALUE}"),
+        Evaluation::Mode(_) if depth == 0 => panic!("{HORIZON_WITHOUT_VALUE}"),
+        Evaluation::ModeWithValue(_, value) if depth == 0 => (value, Flag::Exact),
+        Evaluation::Chance => {
+            let mut value = 0.0;
+            for decision in state.decisions() {
+                let probability = state.probability(&decision);
+                let child_value = alpha_beta_concurrent(
+                    state.choose(decision),
+                    eval,
+                    f64::NEG_INFINITY,
+                    f64::INFINITY,
+                    depth - 1,
+                    table,
+                );
+                value += probability * child_value;
+            }
+            (value, Flag::Exact)
+        }
+        Evaluation::Mode(mode) | Evaluation::ModeWithValue(mode, _) => {
+            let bias = match evaluation {
+                Evaluation::ModeWithValue(_, value) => value,
+                _ => 0.0,
+            };
+            // same window shift by `bias` as in [`alpha_beta`].
+            alpha -= bias;
+            beta -= bias;
+            let mut value = match mode {
+                Mode::Maximize => f64::NEG_INFINITY,
+                Mode::Minimize => f64::INFINITY,
+            };
+            for decision in state.decisions() {
+                let child_value = alpha_beta_concurrent(
+                    state.choose(decision),
+                    eval,
+                    alpha,
+                    beta,
+                    depth - 1,
+                    table,
+                );
+                match mode {
+                    Mode::Maximize => {
+                        value = value.max(child_value);
+                        alpha = alpha.max(value);
+                    }
+                    Mode::Minimize => {
+                        value = value.min(child_value);
+                        beta = beta.min(value);
+                    }
+                }
+                if alpha >= beta {
+                    break;
+                }
+            }
+            value += bias;
+            let flag = if value <= searched_alpha {
+                Flag::UpperBound
+            } else if value >= searched_beta {
+                Flag::LowerBound
+            } else {
+                Flag::Exact
+            };
+            (value, flag)
+        }
+    };
+
+    table.insert(
+        state,
+        TtEntry {
+            value,
+            flag,
+            depth,
+            best: None,
+        },
+    );
+    value
+}
+
+/// like [`choose`], but fans the root decisions out across a rayon thread pool instead
+/// of evaluating them one at a time, each on its own [`alpha_beta_concurrent`] search
+/// against a shared [`ConcurrentTable`].
+///
+/// only supports `ratio == 1.0` (pure minimax), for the same reason [`choose`]'s own
+/// alpha-beta path does: pruning isn't a valid transformation once results are blended
+/// with an expectation. Ties are broken deterministically by comparing `(value,
+/// decision)`, not by whichever worker happens to finish last.
+pub fn choose_parallel<S, E>(eval: E, ratio: f64) -> impl FnMut(S) -> Option<(S::Decision, f64)>
+where
+    S: State + Clone + Eq + Hash + Send + Sync,
+    S::Decision: Clone + Eq + Ord + Send + Sync,
+    E: Eval<S> + Send + Sync,
+{
+    assert!(
+        ratio >= 1.0,
+        "choose_parallel only supports pure minimax search (ratio == 1.0)"
+    );
+
+    let eval = Arc::new(eval);
+    let table: Arc<ConcurrentTable<S>> = Arc::new(DashMap::new());
+
+    move |state| {
+        state
+            .decisions()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|decision| {
+                let value = alpha_beta_concurrent(
+                    state.choose(decision.clone()),
+                    &*eval,
+                    f64::NEG_INFINITY,
+                    f64::INFINITY,
+                    UNBOUNDED_DEPTH,
+                    &table,
+                );
+                (decision, value)
+            })
+            .max_by(rank_decision)
+    }
+}
+
+/// tracks how often an opponent has, in past games, chosen each `Decision` from an
+/// observed `State`, so
+/// future searches can score the opponent's moves against their actual tendencies
+/// instead of always assuming worst-case play. Observations are keyed by the exact
+/// state; states are not grouped or abstracted, so a count only ever applies to the
+/// very state it was recorded in.
+pub struct OpponentModel<S: State> {
+    observations: HashMap<S, HashMap<S::Decision, u32>>,
+}
+
+impl<S> OpponentModel<S>
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone + Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self {
+            observations: HashMap::new(),
+        }
+    }
+
+    /// records that the opponent chose `decision` from `state`.
+    pub fn observe(&mut self, state: S, decision: S::Decision) {
+        *self
+            .observations
+            .entry(state)
+            .or_default()
+            .entry(decision)
+            .or_insert(0) += 1;
+    }
+
+    /// probability distribution over the opponent's next move from `state`, ranging over
+    /// every legal decision, never just the ones observed so far: if `state` has been
+    /// observed before, this is the empirical distribution Laplace-smoothed by one extra
+    /// count per legal decision, so a move that's never been seen still gets a small,
+    /// non-zero probability instead of silently dropping out of the distribution (and,
+    /// for callers like [`opponent_helper`], out of the worst-case it considers).
+    /// Otherwise it's a uniform distribution over the legal decisions.
+    pub fn predict<'a>(&'a self, state: &'a S) -> Box<dyn Iterator<Item = (S::Decision, f64)> + 'a> {
+        match self.observations.get(state) {
+            Some(observed) => {
+                let legal_count = state.decisions().count() as f64;
+                let total = observed.values().sum::<u32>() as f64 + legal_count;
+                Box::new(state.decisions().map(move |decision| {
+                    let count = observed.get(&decision).copied().unwrap_or(0) as f64;
+                    (decision, (count + 1.0) / total)
+                }))
+            }
+            None => {
+                let count = state.decisions().count();
+                let probability = if count == 0 { 0.0 } else { 1.0 / count as f64 };
+                Box::new(state.decisions().map(move |decision| (decision, probability)))
+            }
+        }
+    }
+}
+
+impl<S> Default for OpponentModel<S>
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// scores an opponent (`Mode::Minimize`) node by blending the [`OpponentModel`]'s
+/// predicted expectation with the classic worst-case minimax value: `pessimism == 0.0`
+/// trusts the model outright, `pessimism == 1.0` assumes worst-case play, and anything
+/// in between interpolates. Each child is only evaluated once, so this costs no more
+/// than a plain minimax pass over the same node.
+fn opponent_helper<S>(
+    state: S,
+    model: &OpponentModel<S>,
+    mut eval: impl FnMut(S) -> f64,
+    pessimism: f64,
+) -> f64
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone + Eq + Hash,
+{
+    let mut worst_case = f64::INFINITY;
+    let mut expectation = 0.0;
+    for (decision, probability) in model.predict(&state) {
+        let value = eval(state.choose(decision));
+        worst_case = worst_case.min(value);
+        expectation += probability * value;
+    }
+    pessimism * worst_case + (1.0 - pessimism) * expectation
+}
+
+/// like [`choose`], but scores opponent (`Mode::Minimize`) nodes against an
+/// [`OpponentModel`] instead of assuming worst-case play; `Mode::Maximize` nodes ("us")
+/// are unaffected and still blend minimax with the default expectation via `ratio`,
+/// exactly as in [`choose`]. See [`opponent_helper`] for how `pessimism` is applied.
+pub fn choose_against<S, E: Eval<S>>(
+    eval: E,
+    ratio: f64,
+    model: OpponentModel<S>,
+    pessimism: f64,
+) -> impl FnMut(S) -> Option<(S::Decision, f64)>
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone + Eq + Hash + Ord,
+{
+    fn eval_helper<S: State>(
+        state: S,
+        mut eval: impl FnMut(S) -> f64,
+        fold_value: f64,
+        fold: impl Fn(f64, f64) -> f64,
+        ratio: f64,
+    ) -> f64 {
+        let (minmax, expecto) = state
+            .decisions()
+            .map(|decision| {
+                let probability = state.probability(&decision);
+                (probability, eval(state.choose(decision)))
+            })
+            .fold((fold_value, 0.0), |(f, sum), (probability, value)| {
+                (fold(f, value), sum + probability * value)
+            });
+        ratio * minmax + (1.0 - ratio) * expecto
+    }
+
+    let eval = Rc::new(eval);
+    let model = Rc::new(model);
+    let blend_eval = eval.clone();
+    let blend_model = model.clone();
+    let mut evaluate = cache(move |evaluate, state: S| match blend_eval.evaluate(&state) {
+        Evaluation::Value(value) => value,
+        Evaluation::Chance => chance_helper(state, evaluate),
+        Evaluation::Mode(Mode::Maximize) => {
+            eval_helper(state, evaluate, f64::NEG_INFINITY, f64::max, ratio)
+        }
+        Evaluation::ModeWithValue(Mode::Maximize, value) => {
+            eval_helper(state, evaluate, f64::NEG_INFINITY, f64::max, ratio) + value
+        }
+        Evaluation::Mode(Mode::Minimize) => {
+            opponent_helper(state, &blend_model, evaluate, pessimism)
+        }
+        Evaluation::ModeWithValue(Mode::Minimize, value) => {
+            opponent_helper(state, &blend_model, evaluate, pessimism) + value
+        }
+    });
+
     move |state| {
         state
             .decisions()
             .map(|decision| (decision.clone(), evaluate(state.choose(decision))))
-            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .max_by(rank_decision)
+    }
+}
+
+/// a chosen root decision together with its search value and the principal variation
+/// expected to follow it; see [`choose_with_pv`].
+type ChoiceWithPv<D> = (D, f64, Vec<D>);
+
+/// like [`choose`]'s pure-minimax path, but also returns the principal variation: the
+/// sequence of decisions the search expects both sides to follow after the returned
+/// root move, reconstructed from the [`alpha_beta`] transposition table's `best`
+/// back-pointers.
+///
+/// only supports `ratio == 1.0`: the blended `ratio < 1.0` path has no transposition
+/// table and so nothing to walk a PV out of.
+pub fn choose_with_pv<S, E: Eval<S>>(
+    eval: E,
+    ratio: f64,
+) -> impl FnMut(S) -> Option<ChoiceWithPv<S::Decision>>
+where
+    S: State + Clone + Eq + Hash,
+    S::Decision: Clone + Eq + Hash + Ord,
+{
+    assert!(
+        ratio >= 1.0,
+        "choose_with_pv only supports pure minimax search (ratio == 1.0)"
+    );
+
+    let mut table: HashMap<S, TtEntry<S::Decision>> = HashMap::new();
+
+    move |state| {
+        let (decision, value) = state
+            .decisions()
+            .map(|decision| {
+                let value = alpha_beta(
+                    state.choose(decision.clone()),
+                    &eval,
+                    f64::NEG_INFINITY,
+                    f64::INFINITY,
+                    UNBOUNDED_DEPTH,
+                    &mut table,
+                    None,
+                )
+                .expect("search without a deadline cannot abort");
+                (decision, value)
+            })
+            .max_by(rank_decision)?;
+        let pv = reconstruct_pv(state.choose(decision.clone()), &table);
+        Some((decision, value, pv))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a tiny two-player subtraction (Nim-like) game: each move removes 1 or 2 from the
+    /// pile, and the player who takes the last one wins. Small enough to brute-force
+    /// exhaustively, but deep and branchy enough to exercise the transposition table.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Pile {
+        remaining: u32,
+        to_move: bool,
+    }
+
+    impl State for Pile {
+        type Decision = u32;
+
+        fn decisions(&self) -> impl Iterator<Item = Self::Decision> {
+            (1..=2).filter(|&take| take <= self.remaining)
+        }
+
+        fn choose(&self, decision: Self::Decision) -> Self {
+            Pile {
+                remaining: self.remaining - decision,
+                to_move: !self.to_move,
+            }
+        }
+    }
+
+    /// `to_move == true` is the player the value is computed for; follows the same
+    /// fixed-perspective convention as `main.rs`'s tic-tac-toe evaluator.
+    fn pile_eval(state: &Pile) -> Evaluation {
+        if state.remaining == 0 {
+            Evaluation::Value(if state.to_move { -1.0 } else { 1.0 })
+        } else if state.to_move {
+            Evaluation::Mode(Mode::Maximize)
+        } else {
+            Evaluation::Mode(Mode::Minimize)
+        }
+    }
+
+    /// full-width minimax with no pruning at all, recursing all the way to terminal
+    /// states; used as a ground truth to check [`alpha_beta`] (via [`choose`]) against.
+    fn naive_minimax(state: Pile) -> f64 {
+        match pile_eval(&state) {
+            Evaluation::Value(value) => value,
+            Evaluation::Mode(Mode::Maximize) => state
+                .decisions()
+                .map(|decision| naive_minimax(state.choose(decision)))
+                .fold(f64::NEG_INFINITY, f64::max),
+            Evaluation::Mode(Mode::Minimize) => state
+                .decisions()
+                .map(|decision| naive_minimax(state.choose(decision)))
+                .fold(f64::INFINITY, f64::min),
+            Evaluation::ModeWithValue(_, _) | Evaluation::Chance => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn alpha_beta_matches_naive_minimax() {
+        for remaining in 0..=12 {
+            let state = Pile {
+                remaining,
+                to_move: true,
+            };
+            let expected = naive_minimax(state);
+
+            let mut chooser = choose(pile_eval, 1.0);
+            let actual = match chooser(state) {
+                Some((_, value)) => value,
+                None => naive_minimax(state),
+            };
+
+            assert_eq!(
+                actual, expected,
+                "alpha_beta disagreed with naive minimax for remaining = {remaining}"
+            );
+        }
+    }
+
+    #[test]
+    fn choose_within_respects_deadline_on_blended_path() {
+        // branching factor 6 so a handful of unbounded depth-first passes blow well past
+        // the budget if the deadline isn't actually checked inside the recursion, not
+        // just between iterative-deepening passes.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        struct Wide(u64);
+
+        impl State for Wide {
+            type Decision = u8;
+
+            fn decisions(&self) -> impl Iterator<Item = Self::Decision> {
+                0..6
+            }
+
+            fn choose(&self, decision: Self::Decision) -> Self {
+                Wide(self.0.wrapping_mul(6).wrapping_add(decision as u64))
+            }
+        }
+
+        fn wide_eval(state: &Wide) -> Evaluation {
+            Evaluation::ModeWithValue(Mode::Maximize, (state.0 % 7) as f64)
+        }
+
+        let budget = Duration::from_millis(50);
+        let mut chooser = choose_within(wide_eval, 0.5, budget);
+
+        let start = Instant::now();
+        chooser(Wide(0));
+        let elapsed = start.elapsed();
+
+        assert!(
+            elapsed < budget * 4,
+            "choose_within overran its budget on the ratio < 1.0 path: {elapsed:?} for a {budget:?} budget"
+        );
+    }
+
+    #[test]
+    fn opponent_model_predict_includes_unobserved_legal_moves() {
+        let mut model = OpponentModel::new();
+        let state = Pile {
+            remaining: 3,
+            to_move: false,
+        };
+        // only ever observed taking 1, never 2, from this state.
+        model.observe(state, 1);
+        model.observe(state, 1);
+
+        let predicted: HashMap<_, _> = model.predict(&state).collect();
+        assert_eq!(predicted.len(), 2, "both legal decisions should be predicted");
+        assert!(
+            predicted.get(&2).copied().unwrap_or(0.0) > 0.0,
+            "an unobserved but legal decision must still get nonzero probability, got {predicted:?}"
+        );
     }
 }